@@ -0,0 +1,89 @@
+// src/auth.rs
+use crate::error::ErrorResponse;
+use actix_web::body::EitherBody;
+use actix_web::dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::{Method, StatusCode};
+use actix_web::{Error, HttpResponse};
+use futures_util::future::LocalBoxFuture;
+use std::env;
+use std::future::{ready, Ready};
+use std::rc::Rc;
+
+const API_KEY_HEADER: &str = "x-api-key";
+
+fn is_mutating(method: &Method) -> bool {
+    matches!(*method, Method::POST | Method::PUT | Method::PATCH | Method::DELETE)
+}
+
+/// Requires a matching `X-API-Key` header on state-changing (`POST`, `PUT`,
+/// `PATCH`, `DELETE`) requests, so it can wrap a resource that also serves
+/// unguarded `GET`s (e.g. `/{name}` with both `get_country` and
+/// `delete_country`) without blocking the reads. The expected value is read
+/// from the `API_KEY` environment variable on every request; if it isn't
+/// set, the guard rejects mutating requests rather than leaving them open.
+pub struct ApiKeyGuard;
+
+impl<S, B> Transform<S, ServiceRequest> for ApiKeyGuard
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = ApiKeyGuardMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(ApiKeyGuardMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct ApiKeyGuardMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for ApiKeyGuardMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+
+        if !is_mutating(req.method()) {
+            return Box::pin(async move {
+                service.call(req).await.map(|res| res.map_into_left_body())
+            });
+        }
+
+        let expected = env::var("API_KEY").ok();
+        let provided = req
+            .headers()
+            .get(API_KEY_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+
+        let authorized = matches!((&expected, &provided), (Some(e), Some(p)) if e == p);
+
+        Box::pin(async move {
+            if authorized {
+                service.call(req).await.map(|res| res.map_into_left_body())
+            } else {
+                let response = HttpResponse::build(StatusCode::UNAUTHORIZED).json(ErrorResponse {
+                    error: "Unauthorized".to_string(),
+                    details: Some("Missing or invalid X-API-Key header".to_string()),
+                });
+                Ok(req.into_response(response).map_into_right_body())
+            }
+        })
+    }
+}
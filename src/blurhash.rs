@@ -0,0 +1,119 @@
+// src/blurhash.rs
+use image::{Rgb, RgbImage};
+use std::f64::consts::PI;
+
+const BASE83_CHARS: &[u8] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz#$%*+,-.:;=?@[]^_{|}~";
+
+/// Encodes an RGB image into a compact BlurHash placeholder string: a
+/// `components_x` x `components_y` grid of basis-function-weighted color
+/// averages (the DCT-like components blurhash.org describes), quantized
+/// and packed into base-83 characters.
+pub fn encode(image: &RgbImage, components_x: u32, components_y: u32) -> String {
+    let components_x = components_x.clamp(1, 9);
+    let components_y = components_y.clamp(1, 9);
+
+    let factors = compute_factors(image, components_x, components_y);
+    let dc = factors[0];
+    let ac = &factors[1..];
+
+    let mut hash = String::new();
+    let size_flag = (components_x - 1) + (components_y - 1) * 9;
+    hash.push_str(&base83_encode(size_flag, 1));
+
+    let maximum_value = if ac.is_empty() {
+        hash.push_str(&base83_encode(0, 1));
+        1.0
+    } else {
+        let actual_max = ac
+            .iter()
+            .flat_map(|&(r, g, b)| [r, g, b])
+            .map(f64::abs)
+            .fold(0.0_f64, f64::max);
+        let quantised = ((actual_max * 166.0 - 0.5).floor().clamp(0.0, 82.0)) as u32;
+        hash.push_str(&base83_encode(quantised, 1));
+        (quantised as f64 + 1.0) / 166.0
+    };
+
+    hash.push_str(&base83_encode(encode_dc(dc), 4));
+    for &component in ac {
+        hash.push_str(&base83_encode(encode_ac(component, maximum_value), 2));
+    }
+
+    hash
+}
+
+/// Downscales to a small working grid so the basis-function sums stay cheap,
+/// then computes one weighted color average per component.
+fn compute_factors(image: &RgbImage, components_x: u32, components_y: u32) -> Vec<(f64, f64, f64)> {
+    let small = image::imageops::resize(image, 64, 64, image::imageops::FilterType::Triangle);
+    let (width, height) = small.dimensions();
+
+    let mut factors = Vec::with_capacity((components_x * components_y) as usize);
+    for cy in 0..components_y {
+        for cx in 0..components_x {
+            let normalisation = if cx == 0 && cy == 0 { 1.0 } else { 2.0 };
+            let mut r = 0.0;
+            let mut g = 0.0;
+            let mut b = 0.0;
+
+            for y in 0..height {
+                for x in 0..width {
+                    let basis = normalisation
+                        * (PI * cx as f64 * x as f64 / width as f64).cos()
+                        * (PI * cy as f64 * y as f64 / height as f64).cos();
+                    let Rgb([pr, pg, pb]) = *small.get_pixel(x, y);
+                    r += basis * srgb_to_linear(pr);
+                    g += basis * srgb_to_linear(pg);
+                    b += basis * srgb_to_linear(pb);
+                }
+            }
+
+            let scale = 1.0 / (width * height) as f64;
+            factors.push((r * scale, g * scale, b * scale));
+        }
+    }
+    factors
+}
+
+fn srgb_to_linear(value: u8) -> f64 {
+    let v = value as f64 / 255.0;
+    if v <= 0.04045 {
+        v / 12.92
+    } else {
+        ((v + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(value: f64) -> u32 {
+    let v = value.clamp(0.0, 1.0);
+    let srgb = if v <= 0.0031308 {
+        v * 12.92
+    } else {
+        1.055 * v.powf(1.0 / 2.4) - 0.055
+    };
+    (srgb * 255.0).round().clamp(0.0, 255.0) as u32
+}
+
+fn encode_dc(value: (f64, f64, f64)) -> u32 {
+    let (r, g, b) = value;
+    (linear_to_srgb(r) << 16) | (linear_to_srgb(g) << 8) | linear_to_srgb(b)
+}
+
+fn encode_ac(value: (f64, f64, f64), maximum_value: f64) -> u32 {
+    let quantise = |v: f64| -> u32 {
+        let normalised = v / maximum_value;
+        let signed_sqrt = normalised.signum() * normalised.abs().powf(0.5);
+        ((signed_sqrt * 9.0 + 9.5).floor().clamp(0.0, 18.0)) as u32
+    };
+    quantise(value.0) * 19 * 19 + quantise(value.1) * 19 + quantise(value.2)
+}
+
+fn base83_encode(mut value: u32, length: usize) -> String {
+    let mut result = vec![0u8; length];
+    for slot in result.iter_mut().rev() {
+        *slot = BASE83_CHARS[(value % 83) as usize];
+        value /= 83;
+    }
+    String::from_utf8(result).expect("base83 alphabet is ASCII")
+}
@@ -0,0 +1,86 @@
+// src/error.rs
+use actix_web::http::StatusCode;
+use actix_web::{HttpResponse, ResponseError};
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct ErrorResponse {
+    pub error: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<String>,
+}
+
+/// Crate-wide error type. Handlers return `Result<_, AppError>` and use `?`
+/// instead of matching on every underlying error individually.
+#[derive(Debug)]
+pub enum AppError {
+    NotFound(String),
+    Validation(String),
+    ExternalUnavailable(String),
+    Upstream(StatusCode, String),
+    Internal(String),
+}
+
+impl std::fmt::Display for AppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AppError::NotFound(msg) => write!(f, "{}", msg),
+            AppError::Validation(msg) => write!(f, "{}", msg),
+            AppError::ExternalUnavailable(msg) => write!(f, "{}", msg),
+            AppError::Upstream(_, msg) => write!(f, "{}", msg),
+            AppError::Internal(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl ResponseError for AppError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::Validation(_) => StatusCode::BAD_REQUEST,
+            AppError::ExternalUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            AppError::Upstream(status, _) => *status,
+            AppError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        let (error, details) = match self {
+            AppError::NotFound(msg) => ("Not found".to_string(), Some(msg.clone())),
+            AppError::Validation(msg) => ("Validation failed".to_string(), Some(msg.clone())),
+            AppError::ExternalUnavailable(msg) => {
+                ("External data source unavailable".to_string(), Some(msg.clone()))
+            }
+            AppError::Upstream(_, msg) => {
+                ("External data source unavailable".to_string(), Some(msg.clone()))
+            }
+            AppError::Internal(_) => ("Internal server error".to_string(), None),
+        };
+
+        HttpResponse::build(self.status_code()).json(ErrorResponse { error, details })
+    }
+}
+
+impl From<sqlx::Error> for AppError {
+    fn from(err: sqlx::Error) -> Self {
+        match err {
+            sqlx::Error::RowNotFound => AppError::NotFound("Country not found".to_string()),
+            other => AppError::Internal(other.to_string()),
+        }
+    }
+}
+
+impl From<reqwest::Error> for AppError {
+    fn from(err: reqwest::Error) -> Self {
+        match err.status() {
+            Some(status) => AppError::Upstream(status, err.to_string()),
+            None => AppError::ExternalUnavailable(err.to_string()),
+        }
+    }
+}
+
+impl From<anyhow::Error> for AppError {
+    fn from(err: anyhow::Error) -> Self {
+        AppError::Internal(err.to_string())
+    }
+}
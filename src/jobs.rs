@@ -0,0 +1,103 @@
+// src/jobs.rs
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+}
+
+#[derive(Serialize, Clone)]
+pub struct JobState {
+    pub status: JobStatus,
+    pub queued_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Tracks refresh jobs executed one at a time by a single background
+/// worker. A request that arrives while a job is already queued or running
+/// is coalesced onto that job's id instead of enqueuing a duplicate.
+pub struct JobQueue {
+    sender: mpsc::UnboundedSender<Uuid>,
+    states: Mutex<HashMap<Uuid, JobState>>,
+    in_flight: Mutex<Option<Uuid>>,
+}
+
+impl JobQueue {
+    pub fn new(sender: mpsc::UnboundedSender<Uuid>) -> Self {
+        Self {
+            sender,
+            states: Mutex::new(HashMap::new()),
+            in_flight: Mutex::new(None),
+        }
+    }
+
+    /// Enqueues a new refresh job, or returns the id of the job already in
+    /// flight if one is queued or running.
+    pub fn enqueue(&self) -> Uuid {
+        let mut in_flight = self.in_flight.lock().unwrap();
+        if let Some(id) = *in_flight {
+            return id;
+        }
+
+        let id = Uuid::new_v4();
+        *in_flight = Some(id);
+        self.states.lock().unwrap().insert(
+            id,
+            JobState {
+                status: JobStatus::Queued,
+                queued_at: Utc::now(),
+                started_at: None,
+                finished_at: None,
+                error: None,
+            },
+        );
+        let _ = self.sender.send(id);
+        id
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<JobState> {
+        self.states.lock().unwrap().get(&id).cloned()
+    }
+
+    pub fn mark_running(&self, id: Uuid) {
+        if let Some(state) = self.states.lock().unwrap().get_mut(&id) {
+            state.status = JobStatus::Running;
+            state.started_at = Some(Utc::now());
+        }
+    }
+
+    pub fn mark_succeeded(&self, id: Uuid) {
+        if let Some(state) = self.states.lock().unwrap().get_mut(&id) {
+            state.status = JobStatus::Succeeded;
+            state.finished_at = Some(Utc::now());
+        }
+    }
+
+    pub fn mark_failed(&self, id: Uuid, error: String) {
+        if let Some(state) = self.states.lock().unwrap().get_mut(&id) {
+            state.status = JobStatus::Failed;
+            state.finished_at = Some(Utc::now());
+            state.error = Some(error);
+        }
+    }
+
+    /// Releases the in-flight slot so the next `enqueue` call starts a new job.
+    pub fn release(&self, id: Uuid) {
+        let mut in_flight = self.in_flight.lock().unwrap();
+        if *in_flight == Some(id) {
+            *in_flight = None;
+        }
+    }
+}
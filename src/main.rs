@@ -1,7 +1,16 @@
 // src/main.rs
-use actix_web::{web, App, HttpResponse, HttpServer, Responder, middleware::Logger};
+mod auth;
+mod blurhash;
+mod error;
+mod jobs;
+
+use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer, Responder, middleware::Logger};
+use auth::ApiKeyGuard;
 use actix_web::http::StatusCode;
 use chrono::{DateTime, Utc};
+use error::AppError;
+use futures_util::future::join_all;
+use jobs::JobQueue;
 use rand::Rng;
 use reqwest;
 use serde::{Deserialize, Serialize};
@@ -10,10 +19,13 @@ use std::collections::HashMap;
 use std::env;
 use std::fs;
 use std::io;
+use std::sync::Arc;
+use std::time::SystemTime;
 use anyhow::Result as AnyResult;
 use plotters::prelude::*;
-use actix_web::dev::Server;
 use serde_json::json;
+use tokio::sync::{mpsc, Semaphore};
+use uuid::Uuid;
 
 #[derive(Deserialize)]
 struct ApiCountry {
@@ -49,9 +61,23 @@ struct Country {
     exchange_rate: Option<f64>,
     estimated_gdp: f64,
     flag_url: Option<String>,
+    flag_blurhash: Option<String>,
     last_refreshed_at: DateTime<Utc>,
 }
 
+const FLAG_FETCH_CONCURRENCY: usize = 8;
+const BLURHASH_COMPONENTS_X: u32 = 4;
+const BLURHASH_COMPONENTS_Y: u32 = 3;
+
+/// Downloads a flag image and computes its BlurHash placeholder. Returns
+/// `None` on any fetch or decode failure so one bad flag doesn't fail the
+/// whole refresh.
+async fn fetch_flag_blurhash(url: &str) -> Option<String> {
+    let bytes = reqwest::get(url).await.ok()?.bytes().await.ok()?;
+    let rgb = image::load_from_memory(&bytes).ok()?.to_rgb8();
+    Some(blurhash::encode(&rgb, BLURHASH_COMPONENTS_X, BLURHASH_COMPONENTS_Y))
+}
+
 #[derive(Deserialize)]
 struct QueryParams {
     #[serde(default)]
@@ -68,56 +94,40 @@ struct StatusResponse {
     last_refreshed_at: Option<String>,
 }
 
-#[derive(Serialize)]
-struct ErrorResponse {
-    error: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    details: Option<String>,
+async fn refresh_handler(jobs: web::Data<JobQueue>) -> Result<impl Responder, AppError> {
+    let job_id = jobs.enqueue();
+    Ok(HttpResponse::Accepted().json(json!({"job_id": job_id})))
 }
 
-async fn refresh_handler(
-    pool: web::Data<Pool<MySql>>,
-) -> Result<impl Responder, actix_web::Error> {
+async fn get_job_handler(
+    jobs: web::Data<JobQueue>,
+    path: web::Path<Uuid>,
+) -> Result<impl Responder, AppError> {
+    let state = jobs
+        .get(path.into_inner())
+        .ok_or_else(|| AppError::NotFound("Job not found".to_string()))?;
+    Ok(HttpResponse::Ok().json(state))
+}
+
+/// Fetches countries and exchange rates, stores the result, and refreshes
+/// the summary image. Runs on the background job worker, one job at a time.
+async fn run_refresh(pool: &Pool<MySql>) -> AnyResult<()> {
     let countries_url = "https://restcountries.com/v2/all?fields=name,capital,region,population,flag,currencies";
     let rates_url = "https://open.er-api.com/v6/latest/USD";
 
     // Fetch countries
-    let api_countries: AnyResult<Vec<ApiCountry>> = async {
-        let resp = reqwest::get(countries_url).await.map_err(|e| anyhow::anyhow!("Failed to fetch countries: {}", e))?;
-        if !resp.status().is_success() {
-            return Err(anyhow::anyhow!("Failed to fetch from restcountries.com"));
-        }
-        resp.json().await.map_err(|e| anyhow::anyhow!("Failed to parse countries: {}", e))
-    }.await;
-
-    let api_countries = match api_countries {
-        Ok(countries) => countries,
-        Err(e) => {
-            return Ok(HttpResponse::ServiceUnavailable().json(ErrorResponse {
-                error: "External data source unavailable".to_string(),
-                details: Some(format!("Could not fetch data from restcountries.com: {}", e)),
-            }));
-        }
-    };
+    let resp = reqwest::get(countries_url).await?;
+    if !resp.status().is_success() {
+        return Err(anyhow::anyhow!("Could not fetch data from restcountries.com"));
+    }
+    let api_countries: Vec<ApiCountry> = resp.json().await?;
 
     // Fetch exchange rates
-    let rates_resp: AnyResult<ExchangeRates> = async {
-        let resp = reqwest::get(rates_url).await.map_err(|e| anyhow::anyhow!("Failed to fetch rates: {}", e))?;
-        if !resp.status().is_success() {
-            return Err(anyhow::anyhow!("Failed to fetch from open.er-api.com"));
-        }
-        resp.json().await.map_err(|e| anyhow::anyhow!("Failed to parse rates: {}", e))
-    }.await;
-
-    let rates = match rates_resp {
-        Ok(r) => r,
-        Err(e) => {
-            return Ok(HttpResponse::ServiceUnavailable().json(ErrorResponse {
-                error: "External data source unavailable".to_string(),
-                details: Some(format!("Could not fetch data from open.er-api.com: {}", e)),
-            }));
-        }
-    };
+    let resp = reqwest::get(rates_url).await?;
+    if !resp.status().is_success() {
+        return Err(anyhow::anyhow!("Could not fetch data from open.er-api.com"));
+    }
+    let rates: ExchangeRates = resp.json().await?;
 
     let now = Utc::now();
     let mut processed_countries: Vec<Country> = vec![];
@@ -157,28 +167,38 @@ async fn refresh_handler(
             exchange_rate: exchange_rate_opt,
             estimated_gdp: estimated_gdp_val,
             flag_url: Some(api_c.flag),
+            flag_blurhash: None,
             last_refreshed_at: now,
         };
 
         processed_countries.push(country);
     }
 
-    // Store in DB using transaction
-    match store_countries(&pool, &processed_countries, &now).await {
-        Ok(_) => {
-            // Generate image
-            if let Err(e) = generate_summary_image(&pool, &now).await {
-                log::warn!("Failed to generate summary image: {}", e);
-            }
-            Ok(HttpResponse::Ok().json(json!({"status": "success", "refreshed_at": now.to_rfc3339()})))
-        }
-        Err(e) => {
-            Ok(HttpResponse::InternalServerError().json(ErrorResponse {
-                error: "Internal server error".to_string(),
-                details: None,
-            }))
+    // Fetch flags concurrently (bounded) and compute their BlurHash placeholders.
+    let semaphore = Arc::new(Semaphore::new(FLAG_FETCH_CONCURRENCY));
+    let blurhashes = join_all(processed_countries.iter().map(|country| {
+        let semaphore = Arc::clone(&semaphore);
+        let flag_url = country.flag_url.clone();
+        async move {
+            let url = flag_url?;
+            let _permit = semaphore.acquire().await.ok()?;
+            fetch_flag_blurhash(&url).await
         }
+    }))
+    .await;
+    for (country, blurhash) in processed_countries.iter_mut().zip(blurhashes) {
+        country.flag_blurhash = blurhash;
     }
+
+    // Store in DB using transaction
+    store_countries(pool, &processed_countries, &now).await?;
+
+    // Generate image
+    if let Err(e) = generate_summary_image(pool, &now).await {
+        log::warn!("Failed to generate summary image: {}", e);
+    }
+
+    Ok(())
 }
 
 async fn store_countries(
@@ -199,7 +219,7 @@ async fn store_countries(
             let id: i32 = row.get(0);
             // Update
             sqlx::query(
-                "UPDATE countries SET capital = $1, region = $2, population = $3, currency_code = $4, exchange_rate = $5, estimated_gdp = $6, flag_url = $7, last_refreshed_at = $8 WHERE id = $9"
+                "UPDATE countries SET capital = $1, region = $2, population = $3, currency_code = $4, exchange_rate = $5, estimated_gdp = $6, flag_url = $7, flag_blurhash = $8, last_refreshed_at = $9 WHERE id = $10"
             )
             .bind(&country.capital)
             .bind(&country.region)
@@ -208,6 +228,7 @@ async fn store_countries(
             .bind(&country.exchange_rate)
             .bind(country.estimated_gdp)
             .bind(&country.flag_url)
+            .bind(&country.flag_blurhash)
             .bind(now)
             .bind(id)
             .execute(&mut *tx)
@@ -215,7 +236,7 @@ async fn store_countries(
         } else {
             // Insert
             sqlx::query(
-                "INSERT INTO countries (name, capital, region, population, currency_code, exchange_rate, estimated_gdp, flag_url, last_refreshed_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"
+                "INSERT INTO countries (name, capital, region, population, currency_code, exchange_rate, estimated_gdp, flag_url, flag_blurhash, last_refreshed_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)"
             )
             .bind(&country.name)
             .bind(&country.capital)
@@ -225,6 +246,7 @@ async fn store_countries(
             .bind(&country.exchange_rate)
             .bind(country.estimated_gdp)
             .bind(&country.flag_url)
+            .bind(&country.flag_blurhash)
             .bind(now)
             .execute(&mut *tx)
             .await?;
@@ -236,8 +258,6 @@ async fn store_countries(
 }
 
 async fn generate_summary_image(pool: &Pool<MySql>, now: &DateTime<Utc>) -> AnyResult<()> {
-    fs::create_dir_all("cache").map_err(|e| anyhow::anyhow!("Failed to create cache dir: {}", e))?;
-
     let (total,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM countries")
         .fetch_one(pool)
         .await?;
@@ -255,8 +275,108 @@ async fn generate_summary_image(pool: &Pool<MySql>, now: &DateTime<Utc>) -> AnyR
 
     let timestamp = now.to_rfc3339();
 
-    let root_area = BitMapBackend::new("cache/summary.png", (800, 600))
-        .into_drawing_area();
+    // Rendering and file IO are CPU/IO-bound and synchronous, so they run on
+    // the blocking thread pool instead of tying up the async executor.
+    web::block(move || {
+        render_bar_image(total, &top5, &timestamp)?;
+        render_text_image(total, &top5, &timestamp)?;
+        Ok::<(), anyhow::Error>(())
+    })
+    .await
+    .map_err(|e| anyhow::anyhow!("Image rendering task panicked: {}", e))??;
+
+    Ok(())
+}
+
+/// Which rendering of the summary image a caller gets back from
+/// `GET /countries/image`.
+#[derive(Clone, Copy)]
+enum ImageKind {
+    Bar,
+    Text,
+}
+
+impl ImageKind {
+    fn parse(raw: Option<&str>) -> Self {
+        match raw {
+            Some("text") => ImageKind::Text,
+            _ => ImageKind::Bar,
+        }
+    }
+
+    fn path(self) -> &'static str {
+        match self {
+            ImageKind::Bar => "cache/summary.png",
+            ImageKind::Text => "cache/summary_text.png",
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ImageQuery {
+    #[serde(default)]
+    kind: Option<String>,
+}
+
+/// Draws a labeled bar chart of the top-5 countries by estimated GDP.
+fn render_bar_image(total: i64, top5: &[(String, f64)], timestamp: &str) -> AnyResult<()> {
+    fs::create_dir_all("cache").map_err(|e| anyhow::anyhow!("Failed to create cache dir: {}", e))?;
+
+    let names: Vec<String> = top5.iter().map(|(name, _)| name.clone()).collect();
+    let max_gdp = top5.iter().map(|(_, gdp)| *gdp).fold(0.0_f64, f64::max).max(1.0);
+
+    let root_area = BitMapBackend::new(ImageKind::Bar.path(), (800, 600)).into_drawing_area();
+    root_area.fill(&WHITE)?;
+
+    let mut chart = ChartBuilder::on(&root_area)
+        .caption(
+            format!(
+                "Top 5 Countries by Estimated GDP ({} total, as of {})",
+                total, timestamp
+            ),
+            ("sans-serif", 20).into_font(),
+        )
+        .margin(20)
+        .x_label_area_size(40)
+        .y_label_area_size(80)
+        .build_cartesian_2d((0u32..names.len() as u32).into_segmented(), 0f64..max_gdp * 1.15)?;
+
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .x_desc("Country")
+        .y_desc("Estimated GDP")
+        .x_label_formatter(&|idx| {
+            let i = match idx {
+                SegmentValue::Exact(v) | SegmentValue::CenterOf(v) => *v as usize,
+                SegmentValue::Last => names.len().saturating_sub(1),
+            };
+            names.get(i).cloned().unwrap_or_default()
+        })
+        .draw()?;
+
+    chart.draw_series(top5.iter().enumerate().map(|(i, (_, gdp))| {
+        let i = i as u32;
+        Rectangle::new([(SegmentValue::Exact(i), 0.0), (SegmentValue::Exact(i + 1), *gdp)], BLUE.filled())
+    }))?;
+
+    chart.draw_series(top5.iter().enumerate().map(|(i, (_, gdp))| {
+        Text::new(
+            format!("{:.0}", gdp),
+            (SegmentValue::CenterOf(i as u32), gdp + max_gdp * 0.02),
+            ("sans-serif", 14).into_font(),
+        )
+    }))?;
+
+    root_area.present().map_err(|e| anyhow::anyhow!("Failed to present image: {}", e))?;
+    Ok(())
+}
+
+/// Draws the plain-text summary: total count, top-5 list, last refresh time.
+fn render_text_image(total: i64, top5: &[(String, f64)], timestamp: &str) -> AnyResult<()> {
+    fs::create_dir_all("cache").map_err(|e| anyhow::anyhow!("Failed to create cache dir: {}", e))?;
+
+    let root_area = BitMapBackend::new(ImageKind::Text.path(), (800, 600)).into_drawing_area();
     root_area.fill(&WHITE)?;
 
     let font_style_title = TextStyle::from(("sans-serif", 30).into_font())
@@ -300,8 +420,8 @@ async fn generate_summary_image(pool: &Pool<MySql>, now: &DateTime<Utc>) -> AnyR
 async fn get_countries(
     pool: web::Data<Pool<MySql>>,
     web::Query(params): web::Query<QueryParams>,
-) -> impl Responder {
-    let mut sql = String::from("SELECT id, name, capital, region, population, currency_code, exchange_rate, estimated_gdp, flag_url, last_refreshed_at FROM countries");
+) -> Result<impl Responder, AppError> {
+    let mut sql = String::from("SELECT id, name, capital, region, population, currency_code, exchange_rate, estimated_gdp, flag_url, flag_blurhash, last_refreshed_at FROM countries");
 
     let mut where_clauses = vec![];
     let mut binds: Vec<&(dyn sqlx::Encode<'_, sqlx::MySql> + sqlx::Type<sqlx::MySql> + Sync)> = vec![];
@@ -337,111 +457,165 @@ async fn get_countries(
         query = query.bind(bind);
     }
 
-    match query.fetch_all(&**pool).await {
-        Ok(countries) => HttpResponse::Ok().json(countries),
-        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
-            error: "Internal server error".to_string(),
-            details: None,
-        }),
-    }
+    let countries = query.fetch_all(&**pool).await?;
+    Ok(HttpResponse::Ok().json(countries))
 }
 
 async fn get_country(
     pool: web::Data<Pool<MySql>>,
     path: web::Path<String>,
-) -> impl Responder {
+) -> Result<impl Responder, AppError> {
     let name = path.into_inner();
     if name.trim().is_empty() {
-        return HttpResponse::BadRequest().json(ErrorResponse {
-            error: "Validation failed".to_string(),
-            details: Some("name is required".to_string()),
-        });
+        return Err(AppError::Validation("name is required".to_string()));
     }
 
-    let row = sqlx::query_as::<_, Country>(
-        "SELECT id, name, capital, region, population, currency_code, exchange_rate, estimated_gdp, flag_url, last_refreshed_at FROM countries WHERE LOWER(name) = LOWER($1)"
+    let country = sqlx::query_as::<_, Country>(
+        "SELECT id, name, capital, region, population, currency_code, exchange_rate, estimated_gdp, flag_url, flag_blurhash, last_refreshed_at FROM countries WHERE LOWER(name) = LOWER($1)"
     )
     .bind(&name)
     .fetch_optional(&**pool)
-    .await;
+    .await?
+    .ok_or_else(|| AppError::NotFound("Country not found".to_string()))?;
 
-    match row {
-        Ok(Some(country)) => HttpResponse::Ok().json(country),
-        Ok(None) => HttpResponse::NotFound().json(ErrorResponse {
-            error: "Country not found".to_string(),
-            details: None,
-        }),
-        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
-            error: "Internal server error".to_string(),
-            details: None,
-        }),
-    }
+    Ok(HttpResponse::Ok().json(country))
 }
 
 async fn delete_country(
     pool: web::Data<Pool<MySql>>,
     path: web::Path<String>,
-) -> impl Responder {
+) -> Result<impl Responder, AppError> {
     let name = path.into_inner();
     if name.trim().is_empty() {
-        return HttpResponse::BadRequest().json(ErrorResponse {
-            error: "Validation failed".to_string(),
-            details: Some("name is required".to_string()),
-        });
+        return Err(AppError::Validation("name is required".to_string()));
     }
 
     let result = sqlx::query("DELETE FROM countries WHERE LOWER(name) = LOWER($1)")
         .bind(&name)
         .execute(&**pool)
-        .await;
+        .await?;
 
-    match result {
-        Ok(res) if res.rows_affected() > 0 => HttpResponse::Ok().finish(),
-        Ok(_) => HttpResponse::NotFound().json(ErrorResponse {
-            error: "Country not found".to_string(),
-            details: None,
-        }),
-        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse {
-            error: "Internal server error".to_string(),
-            details: None,
-        }),
+    if result.rows_affected() > 0 {
+        Ok(HttpResponse::Ok().finish())
+    } else {
+        Err(AppError::NotFound("Country not found".to_string()))
     }
 }
 
-async fn status_handler(pool: web::Data<Pool<MySql>>) -> impl Responder {
-    let total_result: Result<(i64,), sqlx::Error> = sqlx::query_as("SELECT COUNT(*) FROM countries")
+async fn status_handler(pool: web::Data<Pool<MySql>>) -> Result<impl Responder, AppError> {
+    let (total,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM countries")
         .fetch_one(&**pool)
-        .await;
+        .await?;
 
-    let last_result: Result<Option<(DateTime<Utc>,)>, sqlx::Error> = sqlx::query_as("SELECT MAX(last_refreshed_at) FROM countries")
+    let last_opt: Option<(DateTime<Utc>,)> = sqlx::query_as("SELECT MAX(last_refreshed_at) FROM countries")
         .fetch_optional(&**pool)
-        .await;
+        .await?;
 
-    match (total_result, last_result) {
-        (Ok((total,)), Ok(last_opt)) => {
-            let last_str = last_opt.map(|(dt,)| dt.to_rfc3339());
-            HttpResponse::Ok().json(StatusResponse {
-                total_countries: total,
-                last_refreshed_at: last_str,
-            })
-        }
-        _ => HttpResponse::InternalServerError().json(ErrorResponse {
-            error: "Internal server error".to_string(),
-            details: None,
-        }),
+    Ok(HttpResponse::Ok().json(StatusResponse {
+        total_countries: total,
+        last_refreshed_at: last_opt.map(|(dt,)| dt.to_rfc3339()),
+    }))
+}
+
+const IMAGE_CACHE_MAX_AGE_SECS: u64 = 300;
+
+fn http_date(time: SystemTime) -> String {
+    httpdate::fmt_http_date(time)
+}
+
+/// Parses a `bytes=start-end` Range header against a known content length,
+/// returning the inclusive `(start, end)` byte span, or `None` if the
+/// header is missing, malformed, or unsatisfiable.
+fn parse_byte_range(header_value: &str, total_len: u64) -> Option<(u64, u64)> {
+    if total_len == 0 {
+        return None;
+    }
+    let spec = header_value.strip_prefix("bytes=")?;
+    let (start_str, end_str) = spec.split_once('-')?;
+
+    let (start, end) = if start_str.is_empty() {
+        let suffix_len: u64 = end_str.parse().ok()?;
+        let suffix_len = suffix_len.min(total_len);
+        (total_len - suffix_len, total_len - 1)
+    } else {
+        let start: u64 = start_str.parse().ok()?;
+        let end: u64 = if end_str.is_empty() {
+            total_len - 1
+        } else {
+            end_str.parse::<u64>().ok()?.min(total_len - 1)
+        };
+        (start, end)
+    };
+
+    if start > end || start >= total_len {
+        None
+    } else {
+        Some((start, end))
     }
 }
 
-async fn image_handler() -> impl Responder {
-    match fs::read("cache/summary.png") {
-        Ok(data) => HttpResponse::Ok()
-            .content_type("image/png")
-            .body(data),
-        Err(_) => HttpResponse::NotFound().json(ErrorResponse {
-            error: "Summary image not found".to_string(),
-            details: None,
-        }),
+async fn image_handler(
+    req: HttpRequest,
+    web::Query(params): web::Query<ImageQuery>,
+) -> Result<impl Responder, AppError> {
+    let image_path = ImageKind::parse(params.kind.as_deref()).path();
+    let metadata = web::block(move || fs::metadata(image_path))
+        .await
+        .map_err(|e| AppError::Internal(format!("Image metadata task panicked: {}", e)))?
+        .map_err(|_| AppError::NotFound("Summary image not found".to_string()))?;
+    let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+    let etag = format!(
+        "\"{:x}-{:x}\"",
+        metadata.len(),
+        modified.duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default().as_secs()
+    );
+    let last_modified = http_date(modified);
+
+    let if_none_match = req.headers().get("if-none-match").and_then(|v| v.to_str().ok());
+    let etag_matches = if_none_match.map_or(false, |v| v.split(',').any(|t| t.trim() == etag));
+
+    let not_modified_since = req
+        .headers()
+        .get("if-modified-since")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| httpdate::parse_http_date(v).ok())
+        .map_or(false, |since| modified <= since);
+
+    if etag_matches || (if_none_match.is_none() && not_modified_since) {
+        return Ok(HttpResponse::NotModified()
+            .insert_header(("ETag", etag))
+            .insert_header(("Last-Modified", last_modified))
+            .finish());
     }
+
+    let data = web::block(move || fs::read(image_path))
+        .await
+        .map_err(|e| AppError::Internal(format!("Image read task panicked: {}", e)))?
+        .map_err(|_| AppError::NotFound("Summary image not found".to_string()))?;
+    let total_len = data.len() as u64;
+
+    let mut response = HttpResponse::Ok();
+    response
+        .content_type("image/png")
+        .insert_header(("ETag", etag))
+        .insert_header(("Last-Modified", last_modified))
+        .insert_header(("Cache-Control", format!("public, max-age={}", IMAGE_CACHE_MAX_AGE_SECS)))
+        .insert_header(("Accept-Ranges", "bytes"));
+
+    if let Some(range) = req
+        .headers()
+        .get("range")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| parse_byte_range(v, total_len))
+    {
+        let (start, end) = range;
+        return Ok(response
+            .status(StatusCode::PARTIAL_CONTENT)
+            .insert_header(("Content-Range", format!("bytes {}-{}/{}", start, end, total_len)))
+            .body(data[start as usize..=end as usize].to_vec()));
+    }
+
+    Ok(response.body(data))
 }
 
 #[actix_web::main]
@@ -459,21 +633,115 @@ async fn main() -> io::Result<()> {
 
     println!("Starting server on port {}", port);
 
+    let (job_tx, mut job_rx) = mpsc::unbounded_channel::<Uuid>();
+    let jobs = web::Data::new(JobQueue::new(job_tx));
+
+    {
+        let pool = pool.clone();
+        let jobs = jobs.clone();
+        tokio::spawn(async move {
+            while let Some(id) = job_rx.recv().await {
+                jobs.mark_running(id);
+                match run_refresh(&pool).await {
+                    Ok(_) => jobs.mark_succeeded(id),
+                    Err(e) => {
+                        log::error!("Refresh job {} failed: {}", id, e);
+                        jobs.mark_failed(id, e.to_string());
+                    }
+                }
+                jobs.release(id);
+            }
+        });
+    }
+
     HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(pool.clone()))
+            .app_data(jobs.clone())
             .wrap(Logger::default())
             .service(
                 web::scope("/countries")
-                    .route("/refresh", web::post().to(refresh_handler))
+                    .service(
+                        web::resource("/refresh")
+                            .wrap(ApiKeyGuard)
+                            .route(web::post().to(refresh_handler))
+                    )
+                    // Literal paths must be registered before the dynamic
+                    // `/{name}` resource below: actix-web's router tries
+                    // resources in registration order and the dynamic
+                    // single-segment pattern also matches `/image`.
+                    .route("/jobs/{id}", web::get().to(get_job_handler))
                     .route("", web::get().to(get_countries))
-                    .route("/{name}", web::get().to(get_country))
-                    .route("/{name}", web::delete().to(delete_country))
                     .route("/image", web::get().to(image_handler))
+                    .service(
+                        web::resource("/{name}")
+                            .wrap(ApiKeyGuard)
+                            .route(web::get().to(get_country))
+                            .route(web::delete().to(delete_country))
+                    )
             )
             .route("/status", web::get().to(status_handler))
     })
     .bind(("0.0.0.0", port))?
     .run()
     .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::http::StatusCode;
+    use actix_web::test;
+
+    async fn ok() -> impl Responder {
+        HttpResponse::Ok().finish()
+    }
+
+    /// Mirrors the `/countries` route topology, including registration order
+    /// and where the API key guard is wrapped, with stub handlers in place
+    /// of the DB-backed ones. This checks routing/shadowing, not handler
+    /// behavior: literal routes (`/image`, `/jobs/{id}`, `""`) must stay
+    /// reachable even though the dynamic `/{name}` resource follows them
+    /// (it also matches `/image`), and the guard on `/{name}` must only
+    /// block the mutating `DELETE`, not `GET`.
+    #[actix_web::test]
+    async fn guarded_routes_dont_shadow_reads() {
+        let app = test::init_service(App::new().service(
+            web::scope("/countries")
+                .service(
+                    web::resource("/refresh")
+                        .wrap(ApiKeyGuard)
+                        .route(web::post().to(ok)),
+                )
+                .route("/jobs/{id}", web::get().to(ok))
+                .route("", web::get().to(ok))
+                .route("/image", web::get().to(ok))
+                .service(
+                    web::resource("/{name}")
+                        .wrap(ApiKeyGuard)
+                        .route(web::get().to(ok))
+                        .route(web::delete().to(ok)),
+                ),
+        ))
+        .await;
+
+        for path in [
+            "/countries",
+            "/countries/image",
+            "/countries/brazil",
+            "/countries/jobs/00000000-0000-0000-0000-000000000000",
+        ] {
+            let req = test::TestRequest::get().uri(path).to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), StatusCode::OK, "GET {} should be reachable", path);
+        }
+
+        let req = test::TestRequest::post().uri("/countries/refresh").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        let req = test::TestRequest::delete().uri("/countries/brazil").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
 }
\ No newline at end of file